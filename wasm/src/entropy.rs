@@ -0,0 +1,99 @@
+use serde::Serialize;
+use wasm_bindgen::prelude::*;
+
+use crate::utils;
+
+// Quick sanity checks on the platform RNG. These are not a statistical test
+// suite; they exist to catch polyfills that are outright broken (returning
+// zeros, a fixed pattern, or the same buffer on every call), which we have seen
+// on some Android WebViews. Thresholds are loose enough that a healthy RNG
+// practically never trips them.
+const SAMPLES: usize = 16;
+const SAMPLE_LEN: usize = 64;
+const BLOCK_LEN: usize = 16;
+// 6 standard deviations of the number of set bits in SAMPLES * SAMPLE_LEN bytes.
+const MAX_BIT_BIAS: usize = 272;
+// A healthy RNG covers ~251 of the 256 byte values in 1 KiB.
+const MIN_DISTINCT_BYTES: usize = 200;
+
+#[wasm_bindgen(typescript_custom_section)]
+const TS_ENTROPY_REPORT: &'static str = r#"
+export interface EntropyReport {
+    ok: boolean;
+    bytes_sampled: number;
+    /** Names of the checks that failed, e.g. "all_zero_sample", "bit_bias". */
+    anomalies: string[];
+}
+"#;
+
+#[derive(Serialize)]
+struct EntropyReport {
+    ok: bool,
+    bytes_sampled: usize,
+    anomalies: Vec<&'static str>,
+}
+
+/// Draws samples from the platform RNG and reports obvious anomalies as a
+/// JSON-encoded `EntropyReport`.
+#[wasm_bindgen]
+pub fn check_entropy() -> Result<String, JsValue> {
+    let report = run_checks();
+    serde_json::to_string(&report).map_err(|e| JsValue::from_str(&e.to_string()))
+}
+
+fn run_checks() -> EntropyReport {
+    let mut samples = Vec::with_capacity(SAMPLES);
+    for _ in 0..SAMPLES {
+        match utils::random_bytes(SAMPLE_LEN) {
+            Ok(sample) => samples.push(sample),
+            Err(_) => {
+                return EntropyReport {
+                    ok: false,
+                    bytes_sampled: samples.len() * SAMPLE_LEN,
+                    anomalies: vec!["rng_unavailable"],
+                }
+            }
+        }
+    }
+
+    let mut anomalies = Vec::new();
+    if samples.iter().any(|s| s.iter().all(|&b| b == 0)) {
+        anomalies.push("all_zero_sample");
+    } else if samples.iter().any(|s| s.iter().all(|&b| b == s[0])) {
+        anomalies.push("constant_sample");
+    }
+    if has_duplicates(samples.iter().map(|s| s.as_slice())) {
+        anomalies.push("repeated_sample");
+    }
+
+    let all: Vec<u8> = samples.concat();
+    if has_duplicates(all.chunks(BLOCK_LEN)) {
+        anomalies.push("repeated_block");
+    }
+
+    let ones: usize = all.iter().map(|b| b.count_ones() as usize).sum();
+    let expected = all.len() * 4;
+    if ones.max(expected) - ones.min(expected) > MAX_BIT_BIAS {
+        anomalies.push("bit_bias");
+    }
+
+    let mut seen = [false; 256];
+    for &b in &all {
+        seen[b as usize] = true;
+    }
+    if seen.iter().filter(|&&s| s).count() < MIN_DISTINCT_BYTES {
+        anomalies.push("low_byte_diversity");
+    }
+
+    EntropyReport {
+        ok: anomalies.is_empty(),
+        bytes_sampled: all.len(),
+        anomalies,
+    }
+}
+
+fn has_duplicates<'a>(items: impl Iterator<Item = &'a [u8]>) -> bool {
+    let mut sorted: Vec<&[u8]> = items.collect();
+    sorted.sort_unstable();
+    sorted.windows(2).any(|w| w[0] == w[1])
+}
@@ -0,0 +1,105 @@
+use wasm_bindgen::prelude::*;
+
+use crate::utils;
+
+// Compact binary envelope profile for constrained transports (MQTT/IoT), where
+// a JSON envelope with base64 fields roughly doubles the message size.
+//
+// Layout:
+//
+//   +--------+-----------+-------------+---------------------+
+//   | header | kid (BE)  | nonce       | ciphertext || tag   |
+//   | 1 byte | 2 bytes   | 12 bytes    | >= 16 bytes         |
+//   +--------+-----------+-------------+---------------------+
+//
+// The high nibble of the header carries the profile version. The low nibble is
+// reserved and must be zero so it can be used for flags later on.
+const PROFILE_VERSION: u8 = 1;
+const HEADER: u8 = PROFILE_VERSION << 4;
+const HEADER_LEN: usize = 1;
+const KID_LEN: usize = 2;
+const NONCE_LEN: usize = 12;
+const TAG_LEN: usize = 16;
+const MIN_ENVELOPE_LEN: usize = HEADER_LEN + KID_LEN + NONCE_LEN + TAG_LEN;
+
+#[wasm_bindgen]
+pub struct CompactEnvelope {
+    kid: u16,
+    nonce: Vec<u8>,
+    ciphertext: Vec<u8>,
+}
+
+#[wasm_bindgen]
+impl CompactEnvelope {
+    #[wasm_bindgen(constructor)]
+    pub fn new(kid: u16, nonce: Vec<u8>, ciphertext: Vec<u8>) -> Result<CompactEnvelope, JsValue> {
+        utils::set_panic_hook();
+        check_parts(&nonce, &ciphertext).map_err(JsValue::from_str)?;
+        Ok(CompactEnvelope {
+            kid,
+            nonce,
+            ciphertext,
+        })
+    }
+
+    /// Parses an envelope previously produced by `encode`.
+    pub fn decode(bytes: &[u8]) -> Result<CompactEnvelope, JsValue> {
+        decode_envelope(bytes).map_err(JsValue::from_str)
+    }
+
+    /// Serializes the envelope into its wire format.
+    pub fn encode(&self) -> Vec<u8> {
+        let mut out =
+            Vec::with_capacity(HEADER_LEN + KID_LEN + self.nonce.len() + self.ciphertext.len());
+        out.push(HEADER);
+        out.extend_from_slice(&self.kid.to_be_bytes());
+        out.extend_from_slice(&self.nonce);
+        out.extend_from_slice(&self.ciphertext);
+        out
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn kid(&self) -> u16 {
+        self.kid
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn nonce(&self) -> Vec<u8> {
+        self.nonce.clone()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn ciphertext(&self) -> Vec<u8> {
+        self.ciphertext.clone()
+    }
+}
+
+fn check_parts(nonce: &[u8], ciphertext: &[u8]) -> Result<(), &'static str> {
+    if nonce.len() != NONCE_LEN {
+        return Err("compact envelope: nonce must be 12 bytes");
+    }
+    if ciphertext.len() < TAG_LEN {
+        return Err("compact envelope: ciphertext is shorter than the AEAD tag");
+    }
+    Ok(())
+}
+
+fn decode_envelope(bytes: &[u8]) -> Result<CompactEnvelope, &'static str> {
+    if bytes.len() < MIN_ENVELOPE_LEN {
+        return Err("compact envelope: input too short");
+    }
+    if bytes[0] >> 4 != PROFILE_VERSION {
+        return Err("compact envelope: unsupported profile version");
+    }
+    if bytes[0] & 0x0f != 0 {
+        return Err("compact envelope: reserved header bits are set");
+    }
+
+    let (kid, rest) = bytes[HEADER_LEN..].split_at(KID_LEN);
+    let (nonce, ciphertext) = rest.split_at(NONCE_LEN);
+    Ok(CompactEnvelope {
+        kid: u16::from_be_bytes([kid[0], kid[1]]),
+        nonce: nonce.to_vec(),
+        ciphertext: ciphertext.to_vec(),
+    })
+}
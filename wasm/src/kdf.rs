@@ -0,0 +1,319 @@
+use std::convert::TryFrom;
+
+use argon2::password_hash::{ParamsString, PasswordHash, SaltString};
+use argon2::{Algorithm, Argon2, Params, Version};
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use hkdf::Hkdf;
+use serde::Serialize;
+use sha2::Sha256;
+use wasm_bindgen::prelude::*;
+use zeroize::Zeroizing;
+
+// Derived keys are always 256 bits so they can be used directly as AES-256 /
+// HMAC-SHA256 keys. The buffers are wiped on drop; only the base64 copy handed
+// to JS survives.
+const KEY_LEN: usize = 32;
+const MIN_SALT_LEN: usize = 16;
+const MIN_PBKDF2_ITERATIONS: u32 = 100_000;
+// Argon2id floor follows the OWASP minimum (19 MiB, 2 passes). The ceiling keeps
+// a bad parameter from exhausting the wasm32 address space.
+pub(crate) const MIN_ARGON2_MEMORY_KIB: u32 = 19_456;
+const MAX_ARGON2_MEMORY_KIB: u32 = 1_048_576;
+pub(crate) const MIN_ARGON2_ITERATIONS: u32 = 2;
+// scrypt is mainly here to read legacy exports, so the floor is the widely used
+// interactive default (N = 2^14) rather than today's recommendation. The
+// ceiling bounds scrypt's 128 * r * N byte working set to 1 GiB.
+const MIN_SCRYPT_LOG_N: u8 = 14;
+const MAX_SCRYPT_MEMORY_BYTES: u64 = 1 << 30;
+// WebAuthn PRF outputs are already uniformly random 32-byte secrets, so HKDF is
+// only there for domain separation: the salt pins keys to this crate and the
+// info string to the caller's purpose.
+const PRF_HKDF_SALT: &[u8] = b"webassembly/webauthn-prf/v1";
+const MIN_PRF_OUTPUT_LEN: usize = 32;
+
+#[wasm_bindgen(typescript_custom_section)]
+const TS_PASSWORD_KEY: &'static str = r#"
+export interface PasswordKey {
+    /** Base64 encoded 32-byte derived key. */
+    key_b64: string;
+    /** PHC string with the KDF parameters and salt, without the hash output. */
+    phc: string;
+}
+"#;
+
+#[derive(Serialize)]
+struct PasswordKey {
+    key_b64: String,
+    phc: String,
+}
+
+/// Derives a 32-byte key from a password with PBKDF2-HMAC-SHA256 and returns it
+/// base64 encoded. The salt must be at least 16 bytes.
+#[wasm_bindgen]
+pub fn derive_key_from_password(
+    password: &str,
+    salt_b64: &str,
+    iterations: u32,
+) -> Result<String, JsValue> {
+    let key = pbkdf2_key(password, salt_b64, iterations).map_err(|e| JsValue::from_str(&e))?;
+    Ok(BASE64.encode(*key))
+}
+
+/// Derives a 32-byte key from a password with Argon2id and returns a
+/// JSON-encoded `PasswordKey` whose PHC string looks like
+/// `$argon2id$v=19$m=...,t=...,p=...$<salt>`.
+///
+/// The PHC string carries the parameters and salt but not the hash output (that
+/// is the key itself), so it can be stored next to the encrypted blob and fed
+/// back into `derive_key_from_phc`.
+#[wasm_bindgen]
+pub fn derive_key_from_password_argon2id(
+    password: &str,
+    salt_b64: &str,
+    memory_kib: u32,
+    iterations: u32,
+    parallelism: u32,
+) -> Result<String, JsValue> {
+    let derived = argon2id_password_key(password, salt_b64, memory_kib, iterations, parallelism)
+        .map_err(|e| JsValue::from_str(&e))?;
+    serde_json::to_string(&derived).map_err(|e| JsValue::from_str(&e.to_string()))
+}
+
+/// Re-derives a base64 key from a password and a PHC string previously returned
+/// by one of the password-based derivation functions.
+#[wasm_bindgen]
+pub fn derive_key_from_phc(password: &str, phc: &str) -> Result<String, JsValue> {
+    let key = phc_key(password, phc).map_err(|e| JsValue::from_str(&e))?;
+    Ok(BASE64.encode(*key))
+}
+
+/// Derives a 32-byte key from a password with scrypt (N = 2^log_n) and returns
+/// a JSON-encoded `PasswordKey` whose PHC string looks like
+/// `$scrypt$ln=...,r=...,p=...$<salt>`.
+#[wasm_bindgen]
+pub fn derive_key_from_password_scrypt(
+    password: &str,
+    salt_b64: &str,
+    log_n: u8,
+    r: u32,
+    p: u32,
+) -> Result<String, JsValue> {
+    let derived =
+        scrypt_password_key(password, salt_b64, log_n, r, p).map_err(|e| JsValue::from_str(&e))?;
+    serde_json::to_string(&derived).map_err(|e| JsValue::from_str(&e.to_string()))
+}
+
+/// Derives a 32-byte key for `purpose` (e.g. "vault") from a WebAuthn PRF /
+/// hmac-secret extension output and returns it base64 encoded. Different
+/// purposes yield independent keys from the same PRF output.
+#[wasm_bindgen]
+pub fn derive_key_from_prf(prf_output: &[u8], purpose: &str) -> Result<String, JsValue> {
+    let key = prf_key(prf_output, purpose).map_err(|e| JsValue::from_str(&e))?;
+    Ok(BASE64.encode(*key))
+}
+
+fn argon2id_password_key(
+    password: &str,
+    salt_b64: &str,
+    memory_kib: u32,
+    iterations: u32,
+    parallelism: u32,
+) -> Result<PasswordKey, String> {
+    let salt = decode_salt(salt_b64)?;
+    let params = argon2id_params(memory_kib, iterations, parallelism)?;
+    let key = argon2id_key(password, &salt, params.clone())?;
+
+    let params = ParamsString::try_from(&params).map_err(|e| e.to_string())?;
+    let phc = phc_string(
+        argon2::ARGON2ID_IDENT,
+        Some(Version::V0x13.into()),
+        params,
+        &salt,
+    )?;
+
+    Ok(PasswordKey {
+        key_b64: BASE64.encode(*key),
+        phc,
+    })
+}
+
+fn scrypt_password_key(
+    password: &str,
+    salt_b64: &str,
+    log_n: u8,
+    r: u32,
+    p: u32,
+) -> Result<PasswordKey, String> {
+    let salt = decode_salt(salt_b64)?;
+    let params = scrypt_params(log_n, r, p)?;
+    let key = scrypt_key(password, &salt, &params)?;
+
+    let params = ParamsString::try_from(params).map_err(|e| e.to_string())?;
+    let phc = phc_string(scrypt::ALG_ID, None, params, &salt)?;
+
+    Ok(PasswordKey {
+        key_b64: BASE64.encode(*key),
+        phc,
+    })
+}
+
+// Builds a PHC string without the hash field: the hash output is the derived
+// key and must not be stored next to the ciphertext.
+fn phc_string(
+    algorithm: argon2::password_hash::Ident<'_>,
+    version: Option<u32>,
+    params: ParamsString,
+    salt: &[u8],
+) -> Result<String, String> {
+    let salt = SaltString::encode_b64(salt).map_err(|e| format!("salt too long: {}", e))?;
+    let phc = PasswordHash {
+        algorithm,
+        version,
+        params,
+        salt: Some(salt.as_salt()),
+        hash: None,
+    };
+    Ok(phc.to_string())
+}
+
+fn phc_key(password: &str, phc: &str) -> Result<Zeroizing<[u8; KEY_LEN]>, String> {
+    let hash = PasswordHash::new(phc.trim()).map_err(|e| format!("invalid PHC string: {}", e))?;
+    let mut salt_buf = [0u8; 64];
+    let salt = hash
+        .salt
+        .ok_or("PHC string has no salt")?
+        .decode_b64(&mut salt_buf)
+        .map_err(|e| format!("invalid PHC salt: {}", e))?;
+    if salt.len() < MIN_SALT_LEN {
+        return Err(format!("salt must be at least {} bytes", MIN_SALT_LEN));
+    }
+
+    if hash.algorithm == argon2::ARGON2ID_IDENT {
+        if hash.version != Some(Version::V0x13.into()) {
+            return Err("unsupported Argon2 version".to_string());
+        }
+        let params =
+            Params::try_from(&hash).map_err(|e| format!("invalid Argon2 params: {}", e))?;
+        // Re-check the floor so a tampered PHC string can't downgrade the KDF.
+        let params = argon2id_params(params.m_cost(), params.t_cost(), params.p_cost())?;
+        argon2id_key(password, salt, params)
+    } else if hash.algorithm == scrypt::ALG_ID {
+        let params =
+            scrypt::Params::try_from(&hash).map_err(|e| format!("invalid scrypt params: {}", e))?;
+        let params = scrypt_params(params.log_n(), params.r(), params.p())?;
+        scrypt_key(password, salt, &params)
+    } else {
+        Err(format!("unsupported PHC algorithm: {}", hash.algorithm))
+    }
+}
+
+pub(crate) fn argon2id_params(
+    memory_kib: u32,
+    iterations: u32,
+    parallelism: u32,
+) -> Result<Params, String> {
+    if !(MIN_ARGON2_MEMORY_KIB..=MAX_ARGON2_MEMORY_KIB).contains(&memory_kib) {
+        return Err(format!(
+            "Argon2id memory must be between {} and {} KiB, got {}",
+            MIN_ARGON2_MEMORY_KIB, MAX_ARGON2_MEMORY_KIB, memory_kib
+        ));
+    }
+    if iterations < MIN_ARGON2_ITERATIONS {
+        return Err(format!(
+            "Argon2id iterations must be at least {}, got {}",
+            MIN_ARGON2_ITERATIONS, iterations
+        ));
+    }
+    Params::new(memory_kib, iterations, parallelism, Some(KEY_LEN))
+        .map_err(|e| format!("invalid Argon2id params: {}", e))
+}
+
+fn argon2id_key(
+    password: &str,
+    salt: &[u8],
+    params: Params,
+) -> Result<Zeroizing<[u8; KEY_LEN]>, String> {
+    let mut key = Zeroizing::new([0u8; KEY_LEN]);
+    Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+        .hash_password_into(password.as_bytes(), salt, &mut *key)
+        .map_err(|e| format!("Argon2id derivation failed: {}", e))?;
+    Ok(key)
+}
+
+fn scrypt_params(log_n: u8, r: u32, p: u32) -> Result<scrypt::Params, String> {
+    if log_n < MIN_SCRYPT_LOG_N {
+        return Err(format!(
+            "scrypt log_n must be at least {}, got {}",
+            MIN_SCRYPT_LOG_N, log_n
+        ));
+    }
+    // 128 * r * 2^log_n; any log_n above 32 is over the limit regardless of r.
+    let memory = (128 * u128::from(r)) << log_n.min(32);
+    if memory > u128::from(MAX_SCRYPT_MEMORY_BYTES) {
+        return Err("scrypt parameters exceed the 1 GiB memory limit".to_string());
+    }
+    scrypt::Params::new(log_n, r, p, KEY_LEN).map_err(|e| format!("invalid scrypt params: {}", e))
+}
+
+fn scrypt_key(
+    password: &str,
+    salt: &[u8],
+    params: &scrypt::Params,
+) -> Result<Zeroizing<[u8; KEY_LEN]>, String> {
+    let mut key = Zeroizing::new([0u8; KEY_LEN]);
+    scrypt::scrypt(password.as_bytes(), salt, params, &mut *key)
+        .map_err(|e| format!("scrypt derivation failed: {}", e))?;
+    Ok(key)
+}
+
+fn prf_key(prf_output: &[u8], purpose: &str) -> Result<Zeroizing<[u8; KEY_LEN]>, String> {
+    if prf_output.len() < MIN_PRF_OUTPUT_LEN {
+        return Err(format!(
+            "PRF output must be at least {} bytes, got {}",
+            MIN_PRF_OUTPUT_LEN,
+            prf_output.len()
+        ));
+    }
+    if purpose.is_empty() {
+        return Err("purpose must not be empty".to_string());
+    }
+
+    let mut key = Zeroizing::new([0u8; KEY_LEN]);
+    Hkdf::<Sha256>::new(Some(PRF_HKDF_SALT), prf_output)
+        .expand(purpose.as_bytes(), &mut *key)
+        .map_err(|e| e.to_string())?;
+    Ok(key)
+}
+
+fn pbkdf2_key(
+    password: &str,
+    salt_b64: &str,
+    iterations: u32,
+) -> Result<Zeroizing<[u8; KEY_LEN]>, String> {
+    if iterations < MIN_PBKDF2_ITERATIONS {
+        return Err(format!(
+            "PBKDF2 iterations must be at least {}, got {}",
+            MIN_PBKDF2_ITERATIONS, iterations
+        ));
+    }
+    let salt = decode_salt(salt_b64)?;
+
+    let mut key = Zeroizing::new([0u8; KEY_LEN]);
+    pbkdf2::pbkdf2_hmac::<Sha256>(password.as_bytes(), &salt, iterations, &mut *key);
+    Ok(key)
+}
+
+fn decode_salt(salt_b64: &str) -> Result<Vec<u8>, String> {
+    let salt = BASE64
+        .decode(salt_b64.trim())
+        .map_err(|e| format!("invalid base64 salt: {}", e))?;
+    if salt.len() < MIN_SALT_LEN {
+        return Err(format!(
+            "salt must be at least {} bytes, got {}",
+            MIN_SALT_LEN,
+            salt.len()
+        ));
+    }
+    Ok(salt)
+}
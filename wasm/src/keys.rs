@@ -0,0 +1,122 @@
+use p256::elliptic_curve::sec1::ToEncodedPoint;
+use rand_core::OsRng;
+use rsa::pkcs8::{
+    DecodePrivateKey, DecodePublicKey, EncodePrivateKey, EncodePublicKey, LineEnding,
+};
+use rsa::{RsaPrivateKey, RsaPublicKey};
+use serde::Serialize;
+use wasm_bindgen::prelude::*;
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+// Anything below 2048 bits is not acceptable for new keys, and generation above
+// 4096 bits takes far too long in wasm to be useful for dev/test harnesses.
+const MIN_RSA_BITS: u32 = 2048;
+const MAX_RSA_BITS: u32 = 4096;
+
+#[wasm_bindgen(typescript_custom_section)]
+const TS_KEY_PAIR_PEM: &'static str = r#"
+export interface KeyPairPem {
+    /** PKCS#8 PEM private key. */
+    private_pem: string;
+    /** SPKI PEM public key. */
+    public_pem: string;
+}
+"#;
+
+#[derive(Serialize, Zeroize, ZeroizeOnDrop)]
+struct KeyPairPem {
+    private_pem: String,
+    public_pem: String,
+}
+
+/// Generates an RSA key pair and returns it as a JSON-encoded `KeyPairPem`.
+#[wasm_bindgen]
+pub fn generate_rsa_keypair(bits: u32) -> Result<String, JsValue> {
+    let pair = rsa_keypair(bits).map_err(|e| JsValue::from_str(&e))?;
+    serde_json::to_string(&pair).map_err(|e| JsValue::from_str(&e.to_string()))
+}
+
+/// Generates a P-256 key pair and returns it as a JSON-encoded `KeyPairPem`.
+#[wasm_bindgen]
+pub fn generate_ec_keypair() -> Result<String, JsValue> {
+    let pair = ec_keypair().map_err(|e| JsValue::from_str(&e))?;
+    serde_json::to_string(&pair).map_err(|e| JsValue::from_str(&e.to_string()))
+}
+
+/// Derives the SPKI PEM public key from a P-256 PKCS#8 PEM private key.
+#[wasm_bindgen]
+pub fn ec_public_key_from_private(private_pem: &str) -> Result<String, JsValue> {
+    let secret = parse_ec_private_key(private_pem).map_err(|e| JsValue::from_str(&e))?;
+    secret
+        .public_key()
+        .to_public_key_pem(LineEnding::LF)
+        .map_err(|e| JsValue::from_str(&format!("SPKI encoding failed: {}", e)))
+}
+
+/// Returns the uncompressed SEC1 point (`0x04 || x || y`) of a P-256 SPKI PEM
+/// public key, the form WebCrypto's `importKey("raw", ...)` expects.
+#[wasm_bindgen]
+pub fn ec_public_key_raw(public_pem: &str) -> Result<Vec<u8>, JsValue> {
+    let public = parse_ec_public_key(public_pem).map_err(|e| JsValue::from_str(&e))?;
+    Ok(public.to_encoded_point(false).as_bytes().to_vec())
+}
+
+pub(crate) fn parse_ec_private_key(pem: &str) -> Result<p256::SecretKey, String> {
+    p256::SecretKey::from_pkcs8_pem(pem.trim())
+        .map_err(|e| format!("invalid P-256 PKCS#8 private key: {}", e))
+}
+
+pub(crate) fn parse_ec_public_key(pem: &str) -> Result<p256::PublicKey, String> {
+    p256::PublicKey::from_public_key_pem(pem.trim())
+        .map_err(|e| format!("invalid P-256 SPKI public key: {}", e))
+}
+
+pub(crate) fn parse_rsa_private_key(pem: &str) -> Result<RsaPrivateKey, String> {
+    RsaPrivateKey::from_pkcs8_pem(pem.trim())
+        .map_err(|e| format!("invalid RSA PKCS#8 private key: {}", e))
+}
+
+pub(crate) fn parse_rsa_public_key(pem: &str) -> Result<RsaPublicKey, String> {
+    RsaPublicKey::from_public_key_pem(pem.trim())
+        .map_err(|e| format!("invalid RSA SPKI public key: {}", e))
+}
+
+fn ec_keypair() -> Result<KeyPairPem, String> {
+    let secret = p256::SecretKey::random(&mut OsRng);
+    let private_pem = secret
+        .to_pkcs8_pem(LineEnding::LF)
+        .map_err(|e| format!("PKCS#8 encoding failed: {}", e))?;
+    let public_pem = secret
+        .public_key()
+        .to_public_key_pem(LineEnding::LF)
+        .map_err(|e| format!("SPKI encoding failed: {}", e))?;
+
+    Ok(KeyPairPem {
+        private_pem: private_pem.to_string(),
+        public_pem,
+    })
+}
+
+fn rsa_keypair(bits: u32) -> Result<KeyPairPem, String> {
+    if !(MIN_RSA_BITS..=MAX_RSA_BITS).contains(&bits) {
+        return Err(format!(
+            "RSA key size must be between {} and {} bits, got {}",
+            MIN_RSA_BITS, MAX_RSA_BITS, bits
+        ));
+    }
+
+    let private_key = RsaPrivateKey::new(&mut OsRng, bits as usize)
+        .map_err(|e| format!("RSA key generation failed: {}", e))?;
+    let private_pem = private_key
+        .to_pkcs8_pem(LineEnding::LF)
+        .map_err(|e| format!("PKCS#8 encoding failed: {}", e))?;
+    let public_pem = private_key
+        .to_public_key()
+        .to_public_key_pem(LineEnding::LF)
+        .map_err(|e| format!("SPKI encoding failed: {}", e))?;
+
+    Ok(KeyPairPem {
+        private_pem: private_pem.to_string(),
+        public_pem,
+    })
+}
@@ -1,7 +1,33 @@
+mod entropy;
+mod envelope;
+mod hash;
+mod kdf;
+mod keys;
+mod mac;
+mod recovery;
+mod sign;
 mod utils;
 
 use wasm_bindgen::prelude::*;
 
+pub use entropy::check_entropy;
+pub use envelope::CompactEnvelope;
+pub use hash::{
+    blake3, blake3_derive_key, blake3_keyed, sha256, sha512, Blake3Hasher, Sha256Hasher,
+    Sha512Hasher,
+};
+pub use kdf::{
+    derive_key_from_password, derive_key_from_password_argon2id, derive_key_from_password_scrypt,
+    derive_key_from_phc, derive_key_from_prf,
+};
+pub use keys::{
+    ec_public_key_from_private, ec_public_key_raw, generate_ec_keypair, generate_rsa_keypair,
+};
+pub use mac::{hmac_sha256, hmac_sha256_verify};
+pub use recovery::{generate_recovery_codes, verify_recovery_code};
+pub use sign::{ecdsa_p256_sign, ecdsa_p256_verify, sign_pss, verify_pss};
+pub use utils::constant_time_eq;
+
 // When the `wee_alloc` feature is enabled, use `wee_alloc` as the global
 // allocator.
 #[cfg(feature = "wee_alloc")]
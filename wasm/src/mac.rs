@@ -0,0 +1,38 @@
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use wasm_bindgen::prelude::*;
+use zeroize::Zeroizing;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Computes HMAC-SHA256 of `data` under a base64 key and returns the base64 tag.
+#[wasm_bindgen]
+pub fn hmac_sha256(key_b64: &str, data: &str) -> Result<String, JsValue> {
+    let mac = keyed_mac(key_b64, data).map_err(|e| JsValue::from_str(&e))?;
+    Ok(BASE64.encode(mac.finalize().into_bytes()))
+}
+
+/// Checks a base64 HMAC-SHA256 tag in constant time. A tag that is not valid
+/// base64 verifies as `false`.
+#[wasm_bindgen]
+pub fn hmac_sha256_verify(key_b64: &str, data: &str, tag_b64: &str) -> Result<bool, JsValue> {
+    let mac = keyed_mac(key_b64, data).map_err(|e| JsValue::from_str(&e))?;
+    let tag = match BASE64.decode(tag_b64.trim()) {
+        Ok(tag) => tag,
+        Err(_) => return Ok(false),
+    };
+    Ok(mac.verify_slice(&tag).is_ok())
+}
+
+fn keyed_mac(key_b64: &str, data: &str) -> Result<HmacSha256, String> {
+    let key = Zeroizing::new(
+        BASE64
+            .decode(key_b64.trim())
+            .map_err(|e| format!("invalid base64 HMAC key: {}", e))?,
+    );
+    let mut mac = HmacSha256::new_from_slice(&key).map_err(|e| e.to_string())?;
+    mac.update(data.as_bytes());
+    Ok(mac)
+}
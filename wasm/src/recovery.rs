@@ -0,0 +1,148 @@
+use std::convert::TryFrom;
+
+use argon2::password_hash::{PasswordHash, PasswordHasher, PasswordVerifier, SaltString};
+use argon2::{Algorithm, Argon2, Params, Version};
+use rand_core::OsRng;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use wasm_bindgen::prelude::*;
+use zeroize::{Zeroize, ZeroizeOnDrop, Zeroizing};
+
+use crate::{kdf, utils};
+
+// Recovery codes are 15 random Crockford base32 symbols (75 bits) plus one
+// check symbol, shown as four groups of four: `7K3M-Q9XD-2HNC-5TWB`. The check
+// symbol catches typos before the (deliberately slow) hash comparison. Stored
+// hashes are Argon2id PHC strings at the crate's minimum Argon2id parameters.
+const ALPHABET: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+const PAYLOAD_LEN: usize = 15;
+const GROUP_LEN: usize = 4;
+const MAX_CODES: u32 = 32;
+
+#[wasm_bindgen(typescript_custom_section)]
+const TS_RECOVERY_CODE: &'static str = r#"
+export interface RecoveryCode {
+    /** Code to show to the user once, e.g. "7K3M-Q9XD-2HNC-5TWB". */
+    code: string;
+    /** Argon2id PHC hash to store server-side. */
+    hash: string;
+}
+"#;
+
+#[derive(Serialize, Zeroize, ZeroizeOnDrop)]
+struct RecoveryCode {
+    code: String,
+    hash: String,
+}
+
+/// Generates `n` recovery codes and returns a JSON-encoded `RecoveryCode[]`.
+#[wasm_bindgen]
+pub fn generate_recovery_codes(n: u32) -> Result<String, JsValue> {
+    let codes = recovery_codes(n).map_err(|e| JsValue::from_str(&e))?;
+    serde_json::to_string(&codes).map_err(|e| JsValue::from_str(&e.to_string()))
+}
+
+/// Checks a user-entered recovery code against its stored hash. Grouping,
+/// case and the usual Crockford confusables (O/0, I/L/1) are ignored. Codes
+/// with a bad check symbol verify as `false`; a malformed hash is an error.
+#[wasm_bindgen]
+pub fn verify_recovery_code(code: &str, stored_hash: &str) -> Result<bool, JsValue> {
+    verify_code(code, stored_hash).map_err(|e| JsValue::from_str(&e))
+}
+
+fn recovery_codes(n: u32) -> Result<Vec<RecoveryCode>, String> {
+    if n == 0 || n > MAX_CODES {
+        return Err(format!(
+            "recovery code count must be between 1 and {}, got {}",
+            MAX_CODES, n
+        ));
+    }
+
+    let hasher = hasher()?;
+    (0..n)
+        .map(|_| {
+            let mut symbols = Zeroizing::new(random_symbols()?);
+            let check = check_symbol(&symbols);
+            symbols.push(check);
+            let salt = SaltString::generate(&mut OsRng);
+            let hash = hasher
+                .hash_password(&symbols, &salt)
+                .map_err(|e| format!("recovery code hashing failed: {}", e))?;
+            Ok(RecoveryCode {
+                code: group(&symbols),
+                hash: hash.to_string(),
+            })
+        })
+        .collect()
+}
+
+fn verify_code(code: &str, stored_hash: &str) -> Result<bool, String> {
+    let hash = PasswordHash::new(stored_hash.trim())
+        .map_err(|e| format!("invalid recovery code hash: {}", e))?;
+    if hash.algorithm != argon2::ARGON2ID_IDENT {
+        return Err(format!("unsupported hash algorithm: {}", hash.algorithm));
+    }
+    // Re-check the floor so a tampered hash can't downgrade the KDF.
+    let params = Params::try_from(&hash).map_err(|e| format!("invalid Argon2 params: {}", e))?;
+    kdf::argon2id_params(params.m_cost(), params.t_cost(), params.p_cost())?;
+
+    let symbols = match normalize(code) {
+        Some(symbols) => Zeroizing::new(symbols),
+        None => return Ok(false),
+    };
+    let (payload, check) = symbols.split_at(PAYLOAD_LEN);
+    if check[0] != check_symbol(payload) {
+        return Ok(false);
+    }
+
+    Ok(Argon2::default().verify_password(&symbols, &hash).is_ok())
+}
+
+fn hasher() -> Result<Argon2<'static>, String> {
+    let params = kdf::argon2id_params(kdf::MIN_ARGON2_MEMORY_KIB, kdf::MIN_ARGON2_ITERATIONS, 1)?;
+    Ok(Argon2::new(Algorithm::Argon2id, Version::V0x13, params))
+}
+
+fn random_symbols() -> Result<Vec<u8>, String> {
+    let bytes = Zeroizing::new(
+        utils::random_bytes(PAYLOAD_LEN).map_err(|e| format!("RNG unavailable: {}", e))?,
+    );
+    // 256 is a multiple of 32, so masking keeps the symbols uniform.
+    Ok(bytes
+        .iter()
+        .map(|b| ALPHABET[(b & 0x1f) as usize])
+        .collect())
+}
+
+fn check_symbol(payload: &[u8]) -> u8 {
+    ALPHABET[(Sha256::digest(payload)[0] & 0x1f) as usize]
+}
+
+fn group(symbols: &[u8]) -> String {
+    symbols
+        .chunks(GROUP_LEN)
+        .map(|chunk| chunk.iter().map(|&b| b as char).collect::<String>())
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+fn normalize(code: &str) -> Option<Vec<u8>> {
+    let mut symbols = Vec::with_capacity(PAYLOAD_LEN + 1);
+    for c in code.chars() {
+        let c = match c.to_ascii_uppercase() {
+            '-' | ' ' => continue,
+            'O' => '0',
+            'I' | 'L' => '1',
+            c => c,
+        };
+        if !c.is_ascii() || !ALPHABET.contains(&(c as u8)) {
+            return None;
+        }
+        symbols.push(c as u8);
+    }
+    if symbols.len() == PAYLOAD_LEN + 1 {
+        Some(symbols)
+    } else {
+        None
+    }
+}
@@ -0,0 +1,75 @@
+use std::convert::TryFrom;
+
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use p256::ecdsa::signature::{RandomizedSigner, SignatureEncoding, Signer, Verifier};
+use p256::ecdsa::{Signature, SigningKey, VerifyingKey};
+use rand_core::OsRng;
+use rsa::pss;
+use sha2::Sha256;
+use wasm_bindgen::prelude::*;
+
+use crate::keys;
+
+// Signatures use the fixed-size IEEE P1363 encoding (r || s, 64 bytes) rather
+// than DER, since that is what WebCrypto's `subtle.sign`/`subtle.verify` use for
+// `{ name: "ECDSA", hash: "SHA-256" }`.
+
+/// Signs `payload` with ECDSA P-256 / SHA-256 using a PKCS#8 PEM private key.
+/// Returns the 64-byte `r || s` signature.
+#[wasm_bindgen]
+pub fn ecdsa_p256_sign(private_pem: &str, payload: &[u8]) -> Result<Vec<u8>, JsValue> {
+    let secret = keys::parse_ec_private_key(private_pem).map_err(|e| JsValue::from_str(&e))?;
+    let signature: Signature = SigningKey::from(secret).sign(payload);
+    Ok(signature.to_bytes().to_vec())
+}
+
+/// Verifies a 64-byte `r || s` ECDSA P-256 / SHA-256 signature against an SPKI
+/// PEM public key. A malformed signature verifies as `false`; a malformed key is
+/// an error.
+#[wasm_bindgen]
+pub fn ecdsa_p256_verify(
+    public_pem: &str,
+    payload: &[u8],
+    signature: &[u8],
+) -> Result<bool, JsValue> {
+    let public = keys::parse_ec_public_key(public_pem).map_err(|e| JsValue::from_str(&e))?;
+    let signature = match Signature::from_slice(signature) {
+        Ok(signature) => signature,
+        Err(_) => return Ok(false),
+    };
+    Ok(VerifyingKey::from(public)
+        .verify(payload, &signature)
+        .is_ok())
+}
+
+// RSA-PSS uses SHA-256 for both the digest and MGF1, with a 32-byte salt. On the
+// WebCrypto side this is `{ name: "RSA-PSS", saltLength: 32 }` with a SHA-256 key.
+
+/// Signs `payload` with RSA-PSS / SHA-256 using a PKCS#8 PEM private key and
+/// returns the base64 signature.
+#[wasm_bindgen]
+pub fn sign_pss(private_key_pem: &str, payload: &str) -> Result<String, JsValue> {
+    let key = keys::parse_rsa_private_key(private_key_pem).map_err(|e| JsValue::from_str(&e))?;
+    let signature =
+        pss::BlindedSigningKey::<Sha256>::new(key).sign_with_rng(&mut OsRng, payload.as_bytes());
+    Ok(BASE64.encode(signature.to_bytes()))
+}
+
+/// Verifies a base64 RSA-PSS / SHA-256 signature against an SPKI PEM public key.
+/// A malformed signature verifies as `false`; a malformed key is an error.
+#[wasm_bindgen]
+pub fn verify_pss(public_key_pem: &str, payload: &str, sig_b64: &str) -> Result<bool, JsValue> {
+    let key = keys::parse_rsa_public_key(public_key_pem).map_err(|e| JsValue::from_str(&e))?;
+    let signature = match BASE64
+        .decode(sig_b64.trim())
+        .ok()
+        .and_then(|bytes| pss::Signature::try_from(bytes.as_slice()).ok())
+    {
+        Some(signature) => signature,
+        None => return Ok(false),
+    };
+    Ok(pss::VerifyingKey::<Sha256>::new(key)
+        .verify(payload.as_bytes(), &signature)
+        .is_ok())
+}
@@ -1,3 +1,6 @@
+use subtle::ConstantTimeEq;
+use wasm_bindgen::prelude::*;
+
 pub fn set_panic_hook() {
     // When the `console_error_panic_hook` feature is enabled, we can call the
     // `set_panic_hook` function at least once during initialization, and then
@@ -8,3 +11,17 @@ pub fn set_panic_hook() {
     #[cfg(feature = "console_error_panic_hook")]
     console_error_panic_hook::set_once();
 }
+
+pub fn random_bytes(len: usize) -> Result<Vec<u8>, getrandom::Error> {
+    let mut buf = vec![0u8; len];
+    getrandom::getrandom(&mut buf)?;
+    Ok(buf)
+}
+
+// Compares two byte strings without branching on their contents, for MACs and
+// tokens where a JS `===` would leak the position of the first mismatch. Only
+// the lengths are compared in variable time.
+#[wasm_bindgen]
+pub fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.ct_eq(b).into()
+}